@@ -1,10 +1,10 @@
 use crate::function::OptionalArg;
-use crate::obj::{objbool, objsequence, objtype::PyClassRef};
+use crate::obj::{objbool, objiter, objsequence, objtype::PyClassRef};
 use crate::pyobject::{IdProtocol, PyClassImpl, PyIterable, PyObjectRef, PyRef, PyResult, PyValue};
 use crate::vm::ReprGuard;
 use crate::VirtualMachine;
 use itertools::Itertools;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::collections::VecDeque;
 
 #[pyclass(name = "deque")]
@@ -12,6 +12,7 @@ use std::collections::VecDeque;
 struct PyDeque {
     deque: RefCell<VecDeque<PyObjectRef>>,
     maxlen: Option<usize>,
+    state: Cell<usize>,
 }
 
 impl PyValue for PyDeque {
@@ -38,12 +39,14 @@ impl PyDeque {
         PyDeque {
             deque: RefCell::new(deque),
             maxlen: maxlen.into_option().and_then(|x| x),
+            state: Cell::new(0),
         }
         .into_ref_with_type(vm, cls)
     }
 
     #[pymethod]
     fn append(&self, obj: PyObjectRef, _vm: &VirtualMachine) {
+        self.bump_state();
         let mut deque = self.deque.borrow_mut();
         if let Some(maxlen) = self.maxlen {
             if deque.len() == maxlen {
@@ -55,6 +58,7 @@ impl PyDeque {
 
     #[pymethod]
     fn appendleft(&self, obj: PyObjectRef, _vm: &VirtualMachine) {
+        self.bump_state();
         let mut deque = self.deque.borrow_mut();
         if let Some(maxlen) = self.maxlen {
             if deque.len() == maxlen {
@@ -66,6 +70,7 @@ impl PyDeque {
 
     #[pymethod]
     fn clear(&self, _vm: &VirtualMachine) {
+        self.bump_state();
         self.deque.borrow_mut().clear()
     }
 
@@ -147,6 +152,7 @@ impl PyDeque {
             idx as usize
         };
 
+        self.bump_state();
         deque.insert(idx, obj);
 
         Ok(())
@@ -154,6 +160,7 @@ impl PyDeque {
 
     #[pymethod]
     fn pop(&self, vm: &VirtualMachine) -> PyResult {
+        self.bump_state();
         self.deque
             .borrow_mut()
             .pop_back()
@@ -162,6 +169,7 @@ impl PyDeque {
 
     #[pymethod]
     fn popleft(&self, vm: &VirtualMachine) -> PyResult {
+        self.bump_state();
         self.deque
             .borrow_mut()
             .pop_front()
@@ -170,6 +178,7 @@ impl PyDeque {
 
     #[pymethod]
     fn remove(&self, obj: PyObjectRef, vm: &VirtualMachine) -> PyResult {
+        self.bump_state();
         let mut deque = self.deque.borrow_mut();
         let mut idx = None;
         for (i, elem) in deque.iter().enumerate() {
@@ -184,27 +193,25 @@ impl PyDeque {
 
     #[pymethod]
     fn reverse(&self, _vm: &VirtualMachine) {
+        self.bump_state();
         self.deque
             .replace_with(|deque| deque.iter().cloned().rev().collect());
     }
 
     #[pymethod]
     fn rotate(&self, mid: OptionalArg<isize>, _vm: &VirtualMachine) {
+        self.bump_state();
         let mut deque = self.deque.borrow_mut();
+        let len = deque.len();
+        if len == 0 {
+            return;
+        }
         let mid = mid.unwrap_or(1);
-        // TODO: once `vecdeque_rotate` lands, use that instead
+        let shift = (mid % len as isize).abs() as usize;
         if mid < 0 {
-            for _ in 0..-mid {
-                if let Some(popped_front) = deque.pop_front() {
-                    deque.push_back(popped_front);
-                }
-            }
+            deque.rotate_left(shift);
         } else {
-            for _ in 0..mid {
-                if let Some(popped_back) = deque.pop_back() {
-                    deque.push_front(popped_back);
-                }
-            }
+            deque.rotate_right(shift);
         }
     }
 
@@ -213,6 +220,194 @@ impl PyDeque {
         self.maxlen
     }
 
+    #[pymethod(name = "__len__")]
+    fn len(&self, _vm: &VirtualMachine) -> usize {
+        self.deque.borrow().len()
+    }
+
+    #[pymethod(name = "__contains__")]
+    fn contains(&self, needle: PyObjectRef, vm: &VirtualMachine) -> PyResult<bool> {
+        for element in self.deque.borrow().iter() {
+            if objbool::boolval(vm, vm._eq(element.clone(), needle.clone())?)? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    #[pymethod(name = "__getitem__")]
+    fn getitem(&self, idx: i32, vm: &VirtualMachine) -> PyResult {
+        let deque = self.deque.borrow();
+        let idx = self.normalize_index(idx, deque.len(), vm)?;
+        Ok(deque.get(idx).unwrap().clone())
+    }
+
+    #[pymethod(name = "__setitem__")]
+    fn setitem(&self, idx: i32, value: PyObjectRef, vm: &VirtualMachine) -> PyResult<()> {
+        let mut deque = self.deque.borrow_mut();
+        let idx = self.normalize_index(idx, deque.len(), vm)?;
+        self.bump_state();
+        deque[idx] = value;
+        Ok(())
+    }
+
+    #[pymethod(name = "__delitem__")]
+    fn delitem(&self, idx: i32, vm: &VirtualMachine) -> PyResult<()> {
+        let mut deque = self.deque.borrow_mut();
+        let idx = self.normalize_index(idx, deque.len(), vm)?;
+        self.bump_state();
+        deque.remove(idx);
+        Ok(())
+    }
+
+    #[pymethod(name = "__iter__")]
+    fn iter(zelf: PyRef<Self>, _vm: &VirtualMachine) -> PyDequeIterator {
+        PyDequeIterator {
+            position: Cell::new(0),
+            state: Cell::new(zelf.state.get()),
+            reversed: false,
+            deque: zelf,
+        }
+    }
+
+    #[pymethod(name = "__reversed__")]
+    fn reversed(zelf: PyRef<Self>, _vm: &VirtualMachine) -> PyDequeIterator {
+        PyDequeIterator {
+            position: Cell::new(0),
+            state: Cell::new(zelf.state.get()),
+            reversed: true,
+            deque: zelf,
+        }
+    }
+
+    #[pymethod(name = "__add__")]
+    fn add(&self, other: PyObjectRef, vm: &VirtualMachine) -> PyResult {
+        let other = match_class!(other,
+            other @ Self => other,
+            _ => return Ok(vm.ctx.not_implemented()),
+        );
+        let mut elements: VecDeque<PyObjectRef> = self.deque.borrow().clone();
+        elements.extend(other.deque.borrow().iter().cloned());
+        Ok(Self::from_elements(elements, self.maxlen, vm)?.into_object())
+    }
+
+    #[pymethod(name = "__iadd__")]
+    fn iadd(zelf: PyRef<Self>, other: PyObjectRef, vm: &VirtualMachine) -> PyResult {
+        let other = match_class!(other,
+            other @ Self => other,
+            _ => return Ok(vm.ctx.not_implemented()),
+        );
+        let elements: Vec<PyObjectRef> = other.deque.borrow().iter().cloned().collect();
+        for elem in elements {
+            zelf.append(elem, vm);
+        }
+        Ok(zelf.into_object())
+    }
+
+    #[pymethod(name = "__mul__")]
+    fn mul(&self, n: isize, vm: &VirtualMachine) -> PyResult {
+        Ok(Self::from_elements(self.repeat(n), self.maxlen, vm)?.into_object())
+    }
+
+    #[pymethod(name = "__rmul__")]
+    fn rmul(&self, n: isize, vm: &VirtualMachine) -> PyResult {
+        self.mul(n, vm)
+    }
+
+    #[pymethod(name = "__imul__")]
+    fn imul(zelf: PyRef<Self>, n: isize, _vm: &VirtualMachine) -> PyRef<Self> {
+        let repeated = zelf.repeat(n);
+        zelf.bump_state();
+        let mut deque = zelf.deque.borrow_mut();
+        *deque = repeated;
+        if let Some(maxlen) = zelf.maxlen {
+            while deque.len() > maxlen {
+                deque.pop_front();
+            }
+        }
+        drop(deque);
+        zelf
+    }
+
+    #[pymethod(name = "__reduce__")]
+    fn reduce(zelf: PyRef<Self>, vm: &VirtualMachine) -> PyResult {
+        let cls = zelf.as_object().class().into_object();
+        let items = vm
+            .ctx
+            .new_list(zelf.deque.borrow().iter().cloned().collect());
+        let maxlen = match zelf.maxlen {
+            Some(maxlen) => vm.ctx.new_int(maxlen),
+            None => vm.get_none(),
+        };
+        let args = vm.ctx.new_tuple(vec![items, maxlen]);
+        Ok(vm
+            .ctx
+            .new_tuple(vec![cls, args, vm.get_none(), vm.get_none()]))
+    }
+
+    #[pymethod(name = "__copy__")]
+    fn copy_(&self, vm: &VirtualMachine) -> Self {
+        self.copy(vm)
+    }
+
+    #[pymethod(name = "__deepcopy__")]
+    fn deepcopy(
+        zelf: PyRef<Self>,
+        memo: PyObjectRef,
+        vm: &VirtualMachine,
+    ) -> PyResult<PyRef<Self>> {
+        let deepcopy = vm.get_attribute(vm.import("copy", &[], 0)?, "deepcopy")?;
+        let mut elements = VecDeque::new();
+        let items: Vec<PyObjectRef> = zelf.deque.borrow().iter().cloned().collect();
+        for elem in items {
+            elements.push_back(vm.invoke(&deepcopy, vec![elem, memo.clone()])?);
+        }
+        Self::from_elements(elements, zelf.maxlen, vm)
+    }
+
+    /// Repeat the current contents `n` times; `n <= 0` yields an empty deque.
+    fn repeat(&self, n: isize) -> VecDeque<PyObjectRef> {
+        let deque = self.deque.borrow();
+        let mut elements = VecDeque::new();
+        for _ in 0..n.max(0) {
+            elements.extend(deque.iter().cloned());
+        }
+        elements
+    }
+
+    /// Build a new deque from `elements`, evicting from the front to honor `maxlen`
+    /// exactly as `append` does.
+    fn from_elements(
+        mut elements: VecDeque<PyObjectRef>,
+        maxlen: Option<usize>,
+        vm: &VirtualMachine,
+    ) -> PyResult<PyRef<Self>> {
+        if let Some(maxlen) = maxlen {
+            while elements.len() > maxlen {
+                elements.pop_front();
+            }
+        }
+        PyDeque {
+            deque: RefCell::new(elements),
+            maxlen,
+            state: Cell::new(0),
+        }
+        .into_ref(vm)
+    }
+
+    fn normalize_index(&self, idx: i32, len: usize, vm: &VirtualMachine) -> PyResult<usize> {
+        let idx = if idx < 0 { idx + len as i32 } else { idx };
+        if idx < 0 || idx as usize >= len {
+            Err(vm.new_index_error("deque index out of range".to_string()))
+        } else {
+            Ok(idx as usize)
+        }
+    }
+
+    fn bump_state(&self) {
+        self.state.set(self.state.get().wrapping_add(1));
+    }
+
     #[pymethod(name = "__repr__")]
     fn repr(zelf: PyRef<Self>, vm: &VirtualMachine) -> PyResult<String> {
         let repr = if let Some(_guard) = ReprGuard::enter(zelf.as_object()) {
@@ -324,8 +519,52 @@ impl PyDeque {
     }
 }
 
+#[pyclass(name = "_deque_iterator")]
+#[derive(Debug)]
+struct PyDequeIterator {
+    position: Cell<usize>,
+    deque: PyRef<PyDeque>,
+    state: Cell<usize>,
+    reversed: bool,
+}
+
+impl PyValue for PyDequeIterator {
+    fn class(vm: &VirtualMachine) -> PyClassRef {
+        vm.class("_collections", "_deque_iterator")
+    }
+}
+
+#[pyimpl]
+impl PyDequeIterator {
+    #[pymethod(name = "__next__")]
+    fn next(&self, vm: &VirtualMachine) -> PyResult {
+        if self.state.get() != self.deque.state.get() {
+            return Err(vm.new_runtime_error("deque mutated during iteration".to_string()));
+        }
+        let deque = self.deque.deque.borrow();
+        let pos = self.position.get();
+        if pos >= deque.len() {
+            return Err(objiter::new_stop_iteration(vm));
+        }
+        let idx = if self.reversed {
+            deque.len() - 1 - pos
+        } else {
+            pos
+        };
+        let obj = deque.get(idx).unwrap().clone();
+        self.position.set(pos + 1);
+        Ok(obj)
+    }
+
+    #[pymethod(name = "__iter__")]
+    fn iter(zelf: PyRef<Self>, _vm: &VirtualMachine) -> PyRef<Self> {
+        zelf
+    }
+}
+
 pub fn make_module(vm: &VirtualMachine) -> PyObjectRef {
     py_module!(vm, "_collections", {
         "deque" => PyDeque::make_class(&vm.ctx),
+        "_deque_iterator" => PyDequeIterator::make_class(&vm.ctx),
     })
 }